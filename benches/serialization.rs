@@ -0,0 +1,58 @@
+use std::str::FromStr;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use pngme::chunk::Chunk;
+use pngme::chunk_type::ChunkType;
+use pngme::png::Png;
+
+/// A png with a single large chunk, big enough to make the difference
+/// between allocating per-chunk buffers and writing straight through show
+/// up in the benchmark.
+fn large_png() -> Png {
+    let data = vec![42u8; 8 * 1024 * 1024];
+    let chunk = Chunk::new(ChunkType::from_str("LArG").unwrap(), data);
+    Png::from_chunks(vec![chunk])
+}
+
+/// Serializes `png` the way this crate did before [`Png::write_to`]
+/// existed: each chunk's bytes are assembled through `.iter().chain(...)`
+/// into a throwaway `Vec`, then every chunk's `Vec` is chained again into
+/// the final buffer. Kept here only as a baseline for `bench_write_to` to
+/// compare against, now that `Chunk`/`Png` no longer have this code path.
+fn bytes_via_intermediate_vecs(png: &Png) -> Vec<u8> {
+    Png::STANDARD_HEADER
+        .iter()
+        .copied()
+        .chain(png.chunks().iter().flat_map(|chunk| {
+            let length: Vec<u8> = chunk.length().to_be_bytes().to_vec();
+            let crc: Vec<u8> = chunk.crc().to_be_bytes().to_vec();
+            length
+                .into_iter()
+                .chain(chunk.chunk_type().bytes())
+                .chain(chunk.data().iter().copied())
+                .chain(crc)
+                .collect::<Vec<u8>>()
+        }))
+        .collect()
+}
+
+fn bench_as_bytes(c: &mut Criterion) {
+    let png = large_png();
+    c.bench_function("as_bytes (intermediate Vec per chunk)", |b| {
+        b.iter(|| black_box(bytes_via_intermediate_vecs(&png)))
+    });
+}
+
+fn bench_write_to(c: &mut Criterion) {
+    let png = large_png();
+    c.bench_function("write_to (direct sink)", |b| {
+        b.iter(|| {
+            let mut out = Vec::new();
+            png.write_to(&mut out).unwrap();
+            black_box(out)
+        })
+    });
+}
+
+criterion_group!(benches, bench_as_bytes, bench_write_to);
+criterion_main!(benches);
@@ -1,4 +1,4 @@
-use clap::{Parser, command, Subcommand, Args};
+use clap::{Parser, Subcommand, Args};
 
 
 #[derive(Parser)]
@@ -23,13 +23,25 @@ pub struct EncodeCommand {
 	pub image_path: String,
 	pub chunk_type: String,
 	pub message: String,
-	pub output_file: Option<String>
+	pub output_file: Option<String>,
+
+	/// Encode the message as a structured TLV payload instead of raw bytes
+	#[arg(long)]
+	pub structured: bool,
+
+	/// Maximum bytes of data per chunk, including the 8-byte segment header; longer messages are split across multiple chunks
+	#[arg(long, default_value_t = crate::framing::DEFAULT_MAX_CHUNK_SIZE)]
+	pub max_chunk_size: usize
 }
 
 #[derive(Args, Debug)]
 pub struct DecodeCommand {
 	pub image_path: String,
-	pub chunk_type: String
+	pub chunk_type: String,
+
+	/// Decode the chunk's data as a structured TLV payload
+	#[arg(long)]
+	pub structured: bool
 }
 
 #[derive(Args, Debug)]
@@ -1,8 +1,9 @@
 use std::fmt::Display;
+use std::io::{self, Write};
 
 use crc::Crc;
 
-use crate::chunk_type::{ChunkType, self};
+use crate::chunk_type::ChunkType;
 
 use crate::{Result, Error};
 
@@ -16,18 +17,15 @@ pub struct Chunk {
 
 impl Chunk {
     pub fn new(chunk_type: ChunkType, data: Vec<u8>) -> Self {
-        let crc = Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
-        let chunk_data: Vec<u8> = []
-        .iter()
-        .chain(chunk_type.bytes().iter())
-        .chain(data.iter())
-        .copied()
-        .collect();
+        let crc_alg = Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+        let mut digest = crc_alg.digest();
+        digest.update(&chunk_type.bytes());
+        digest.update(&data);
 
 		Self {
             chunk_type,
             length: data.len() as u32,
-            crc: crc.checksum(&chunk_data),
+            crc: digest.finalize(),
             data
         }
 	}
@@ -52,42 +50,65 @@ impl Chunk {
         Ok(self.data.clone().iter().map(|&c| c as char).collect())
 	}
 
+    /// Writes this chunk's wire format (length, type, data, crc) to `w`
+    /// without building an intermediate buffer.
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&self.length.to_be_bytes())?;
+        w.write_all(&self.chunk_type.bytes())?;
+        w.write_all(&self.data)?;
+        w.write_all(&self.crc.to_be_bytes())?;
+        Ok(())
+    }
+
     pub fn as_bytes(&self) -> Vec<u8> {
-		self.length
-            .to_be_bytes()
-            .iter()
-            .chain(self.chunk_type.bytes().iter())
-            .chain(self.data.iter())
-            .chain(self.crc.to_be_bytes().iter())
-            .copied()
-            .collect()
+        let mut buf = Vec::with_capacity(self.data.len() + 12);
+        self.write_to(&mut buf)
+            .expect("writing to a Vec<u8> cannot fail");
+        buf
 	}
 }
 
+/// Returns `buf[range]`, or a [`ChunkError::NotEnoughData`] instead of
+/// panicking if `range` runs past the end of `buf`.
+fn take(buf: &[u8], range: std::ops::Range<usize>) -> Result<&[u8]> {
+    if range.end > buf.len() {
+        return Err(Box::new(ChunkError::NotEnoughData {
+            offset: range.start,
+            needed: range.end - range.start,
+            available: buf.len().saturating_sub(range.start),
+        }));
+    }
+
+    Ok(&buf[range])
+}
+
+/// Reads a big-endian `u32` at `offset`, bounds-checked like [`take`].
+fn read_u32_be(buf: &[u8], offset: usize) -> Result<u32> {
+    let bytes = take(buf, offset..(offset + 4))?;
+    Ok(u32::from_be_bytes(bytes.try_into().unwrap()))
+}
+
 impl TryFrom<&[u8]> for Chunk {
     type Error = Error;
 
     fn try_from(value: &[u8]) -> Result<Self> {
-        if value.len() < 12 {
-            return Err(Box::new(ChunkError::ByteLengthError(value.len())));
-        }
+        let data_length = read_u32_be(value, 0)?;
+        let chunk_type_bytes: [u8; 4] = take(value, 4..8)?.try_into().unwrap();
+        let chunk_type = ChunkType::try_from(chunk_type_bytes)?;
+        let chunk_data = take(value, 8..(data_length as usize + 8))?.to_vec();
+        let crc = read_u32_be(value, data_length as usize + 8)?;
 
-        let data_length = u32::from_be_bytes(value[0..4].try_into().unwrap());
-        let chunk_type_bytes: [u8; 4] = value[4..8].try_into().unwrap();
-        let chunk_type = ChunkType::try_from(chunk_type_bytes).unwrap();
-        let chunk_data: Vec<u8> = value[8..(data_length as usize + 8)].to_vec();
-        let crc = u32::from_be_bytes(value[(data_length as usize + 8)..(data_length as usize + 12)].try_into().unwrap());
         let crc_alg = Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
-        let actual_crc = crc_alg.checksum(&value[4..(data_length as usize + 8)]);
+        let actual_crc = crc_alg.checksum(take(value, 4..(data_length as usize + 8))?);
         if crc != actual_crc {
             return Err(Box::new(ChunkError::MismatchedCrcError(actual_crc)));
         }
 
         Ok(Self {
             length: data_length,
-            chunk_type: chunk_type,
+            chunk_type,
             data: chunk_data,
-            crc: crc
+            crc,
         })
     }
 }
@@ -98,11 +119,17 @@ impl Display for Chunk {
     }
 }
 
-/// Chunk type errors
+/// Chunk parsing errors
 #[derive(Debug)]
 pub enum ChunkError {
-    /// Chunk has incorrect number of bytes (4 expected)
-    ByteLengthError(usize),
+    /// A read at `offset` needed `needed` bytes but the buffer only had
+    /// `available` bytes left: the buffer is too short for the length and
+    /// type fields, or shorter than the declared length says.
+    NotEnoughData {
+        offset: usize,
+        needed: usize,
+        available: usize,
+    },
 
     /// The input string contains an invalid character at the given index
     MismatchedCrcError(u32),
@@ -113,10 +140,14 @@ impl std::error::Error for ChunkError {}
 impl Display for ChunkError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            ChunkError::ByteLengthError(actual) => write!(
+            ChunkError::NotEnoughData {
+                offset,
+                needed,
+                available,
+            } => write!(
                 f,
-                "Expected 4 bytes but received {} when creating chunk type",
-                actual
+                "Expected {} bytes at offset {} but only {} were available",
+                needed, offset, available
             ),
             ChunkError::MismatchedCrcError(actual) => {
                 write!(f, "Provided crc does not match actual chuck's data crc: {}", actual)
@@ -232,6 +263,46 @@ mod tests {
         assert!(chunk.is_err());
     }
 
+    #[test]
+    fn test_chunk_from_empty_bytes() {
+        let chunk = Chunk::try_from(&[][..]);
+        assert!(chunk.is_err());
+    }
+
+    #[test]
+    fn test_chunk_from_truncated_header() {
+        // A declared length and type with no data or crc following them.
+        let chunk_data: Vec<u8> = vec![0, 0, 0, 42, b'R', b'u', b'S', b't'];
+        let chunk = Chunk::try_from(chunk_data.as_ref());
+        assert!(chunk.is_err());
+    }
+
+    #[test]
+    fn test_chunk_from_truncated_data() {
+        let data_length: u32 = 42;
+        let chunk_type = "RuSt".as_bytes();
+        let message_bytes = "too short".as_bytes();
+
+        let chunk_data: Vec<u8> = data_length
+            .to_be_bytes()
+            .iter()
+            .chain(chunk_type.iter())
+            .chain(message_bytes.iter())
+            .copied()
+            .collect();
+
+        let chunk = Chunk::try_from(chunk_data.as_ref());
+        assert!(chunk.is_err());
+    }
+
+    #[test]
+    fn test_chunk_from_oversized_length() {
+        // A declared length far larger than any buffer we could be handed.
+        let chunk_data: Vec<u8> = vec![0xFF, 0xFF, 0xFF, 0xFF, b'R', b'u', b'S', b't', 1, 2, 3];
+        let chunk = Chunk::try_from(chunk_data.as_ref());
+        assert!(chunk.is_err());
+    }
+
     #[test]
     pub fn test_chunk_trait_impls() {
         let data_length: u32 = 42;
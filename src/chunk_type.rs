@@ -32,16 +32,14 @@ impl FromStr for ChunkType {
 
 		let valid_chars = s.as_bytes()
 		.iter()
-		.all(|&c| c >= b'a' && c <= b'z' || c >= b'A' && c <= b'Z');
+		.all(|&c| c.is_ascii_lowercase() || c.is_ascii_uppercase());
 
 		if !valid_chars {
 			return Err(Box::new(ChunkTypeError::InvalidCharacter));
 		}
 
-		let mut i = 0;
-		for &b in s.as_bytes() {
+		for (i, &b) in s.as_bytes().iter().enumerate() {
 			chars[i] = b;
-			i +=1;
 		}
 
         Ok(Self(chars))
@@ -52,8 +50,8 @@ impl FromStr for ChunkType {
 
 impl Display for ChunkType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for c in self.0  {
-			write!(f, "{}", c as char);
+        for c in self.0 {
+			write!(f, "{}", c as char)?;
 		}
 		Ok(())
     }
@@ -68,7 +66,7 @@ impl ChunkType {
 		let valid_chars =
 		self.0
 		.iter()
-		.all(|&c| c >= b'a' && c <= b'z' || c >= b'A' && c <= b'Z');
+		.all(|&c| c.is_ascii_lowercase() || c.is_ascii_uppercase());
 
 		self.is_reserved_bit_valid() && valid_chars
 	}
@@ -0,0 +1,285 @@
+use std::io::{self, Read};
+
+use crc::Crc;
+
+use crate::chunk::Chunk;
+use crate::png::{DecodeReport, DropReason, DroppedChunk, Png, PngError};
+use crate::Result;
+
+/// Size of the fixed buffer chunk bytes are read through, independent of
+/// any chunk's declared length.
+const READ_BUF_SIZE: usize = 8 * 1024;
+
+/// Incrementally decodes a png from any [`Read`] source using a small,
+/// fixed-size buffer instead of slurping the whole file into memory.
+///
+/// Yields one [`Chunk`] at a time, in file order, so callers like `decode`
+/// that only need the chunks of a given type can stop pulling from the
+/// iterator once they have them, without parsing the rest of a large
+/// file. A chunk
+/// whose CRC does not match is skipped rather than treated as fatal;
+/// skipped chunks accumulate in [`PngDecoder::report`].
+pub struct PngDecoder<R> {
+    reader: R,
+    started: bool,
+    finished: bool,
+    report: DecodeReport,
+}
+
+impl<R: Read> PngDecoder<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            started: false,
+            finished: false,
+            report: DecodeReport::default(),
+        }
+    }
+
+    /// Chunks dropped so far because of a bad CRC or a truncated stream.
+    pub fn report(&self) -> &DecodeReport {
+        &self.report
+    }
+
+    fn read_signature(&mut self) -> Result<()> {
+        let mut header = [0u8; 8];
+        self.reader.read_exact(&mut header)?;
+        if header != Png::STANDARD_HEADER {
+            return Err(Box::new(PngError::InvalidHeader));
+        }
+        Ok(())
+    }
+
+    /// Reads up to `buf.len()` bytes, stopping early only at a clean EOF.
+    fn read_fill(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut read = 0;
+        while read < buf.len() {
+            match self.reader.read(&mut buf[read..])? {
+                0 => break,
+                n => read += n,
+            }
+        }
+        Ok(read)
+    }
+
+    /// Reads the next chunk's raw bytes (length + type + data + crc).
+    /// Returns `Ok(None)` at a clean end of stream, and records a dropped
+    /// chunk if the stream ends partway through one instead of erroring.
+    ///
+    /// The declared length comes straight off the wire and can't be
+    /// trusted, so `rest` is filled through a small fixed-size buffer
+    /// rather than allocated up front to that size: a forged multi-gigabyte
+    /// length on an otherwise tiny stream fails fast as a `LengthOverrun`
+    /// instead of attempting a multi-gigabyte allocation.
+    ///
+    /// This only guards against a *forged* length outrunning the stream;
+    /// it doesn't cap memory use for a genuinely large, honestly-declared
+    /// chunk (e.g. a multi-hundred-MB `IDAT`), which is still grown to its
+    /// full size in `rest` before `Chunk::try_from` runs.
+    fn read_chunk_bytes(&mut self) -> Result<Option<Vec<u8>>> {
+        let mut length_bytes = [0u8; 4];
+        if self.read_fill(&mut length_bytes)? == 0 {
+            return Ok(None);
+        }
+
+        let declared_length = u32::from_be_bytes(length_bytes);
+        let expected = 4 + declared_length as usize + 4;
+
+        let mut rest = Vec::new();
+        let mut buf = [0u8; READ_BUF_SIZE];
+        while rest.len() < expected {
+            let want = (expected - rest.len()).min(buf.len());
+            let read = self.read_fill(&mut buf[..want])?;
+            rest.extend_from_slice(&buf[..read]);
+            if read < want {
+                break;
+            }
+        }
+
+        if rest.len() != expected {
+            let chunk_type = if rest.len() >= 4 {
+                String::from_utf8_lossy(&rest[..4]).into_owned()
+            } else {
+                String::new()
+            };
+            self.report.dropped.push(DroppedChunk {
+                chunk_type,
+                reason: DropReason::LengthOverrun {
+                    declared: declared_length,
+                    available: rest.len(),
+                },
+            });
+            return Ok(None);
+        }
+
+        let mut bytes = Vec::with_capacity(length_bytes.len() + rest.len());
+        bytes.extend_from_slice(&length_bytes);
+        bytes.extend_from_slice(&rest);
+        Ok(Some(bytes))
+    }
+}
+
+impl<R: Read> Iterator for PngDecoder<R> {
+    type Item = Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        if !self.started {
+            self.started = true;
+            if let Err(e) = self.read_signature() {
+                self.finished = true;
+                return Some(Err(e));
+            }
+        }
+
+        loop {
+            let bytes = match self.read_chunk_bytes() {
+                Ok(Some(bytes)) => bytes,
+                Ok(None) => {
+                    self.finished = true;
+                    return None;
+                }
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                }
+            };
+
+            match Chunk::try_from(bytes.as_slice()) {
+                Ok(chunk) => return Some(Ok(chunk)),
+                Err(_) => {
+                    let chunk_type = String::from_utf8_lossy(&bytes[4..8]).into_owned();
+                    let crc_val = u32::from_be_bytes(bytes[bytes.len() - 4..].try_into().unwrap());
+                    let crc_sum = Crc::<u32>::new(&crc::CRC_32_ISO_HDLC)
+                        .checksum(&bytes[4..bytes.len() - 4]);
+                    self.report.dropped.push(DroppedChunk {
+                        chunk_type,
+                        reason: DropReason::CrcMismatch { crc_val, crc_sum },
+                    });
+                    // keep looping so one damaged chunk doesn't stop the scan
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_type::ChunkType;
+    use std::str::FromStr;
+
+    fn chunk_from_strings(chunk_type: &str, data: &str) -> Chunk {
+        let chunk_type = ChunkType::from_str(chunk_type).unwrap();
+        Chunk::new(chunk_type, data.bytes().collect())
+    }
+
+    fn sample_png_bytes() -> Vec<u8> {
+        let chunks = [
+            chunk_from_strings("FrSt", "first"),
+            chunk_from_strings("miDl", "middle"),
+            chunk_from_strings("LASt", "last"),
+        ];
+
+        Png::STANDARD_HEADER
+            .iter()
+            .copied()
+            .chain(chunks.iter().flat_map(Chunk::as_bytes))
+            .collect()
+    }
+
+    #[test]
+    fn test_decoder_yields_all_chunks_in_order() {
+        let bytes = sample_png_bytes();
+        let decoder = PngDecoder::new(bytes.as_slice());
+
+        let types: Vec<String> = decoder
+            .map(|chunk| chunk.unwrap().chunk_type().to_string())
+            .collect();
+
+        assert_eq!(types, vec!["FrSt", "miDl", "LASt"]);
+    }
+
+    #[test]
+    fn test_decoder_stops_early_once_match_found() {
+        let bytes = sample_png_bytes();
+        let mut decoder = PngDecoder::new(bytes.as_slice());
+
+        let found = decoder.find(|chunk| {
+            chunk
+                .as_ref()
+                .map(|c| c.chunk_type().to_string() == "miDl")
+                .unwrap_or(false)
+        });
+
+        assert!(found.is_some());
+    }
+
+    #[test]
+    fn test_decoder_rejects_bad_signature() {
+        let mut bytes = sample_png_bytes();
+        bytes[0] = 0;
+        let mut decoder = PngDecoder::new(bytes.as_slice());
+
+        assert!(decoder.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_decoder_skips_bad_crc_and_reports_it() {
+        let mut bytes = sample_png_bytes();
+        let crc_index = 8 + chunk_from_strings("FrSt", "first").as_bytes().len() - 1;
+        bytes[crc_index] = bytes[crc_index].wrapping_add(1);
+
+        let mut decoder = PngDecoder::new(bytes.as_slice());
+        let first = decoder.next().unwrap().unwrap();
+
+        assert_eq!(first.chunk_type().to_string(), "miDl");
+        assert_eq!(decoder.report().dropped.len(), 1);
+        assert_eq!(decoder.report().dropped[0].chunk_type, "FrSt");
+    }
+
+    #[test]
+    fn test_decoder_handles_forged_length_without_large_allocation() {
+        // A declared length near u32::MAX on a stream that only has a
+        // handful of bytes left must not be taken as a signal to allocate
+        // gigabytes; it should just report a length overrun.
+        let mut bytes = Png::STANDARD_HEADER.to_vec();
+        bytes.extend_from_slice(&u32::MAX.to_be_bytes());
+        bytes.extend_from_slice(b"FrSt");
+        bytes.extend_from_slice(b"only a few bytes");
+
+        let mut decoder = PngDecoder::new(bytes.as_slice());
+
+        assert!(decoder.next().is_none());
+        assert_eq!(decoder.report().dropped.len(), 1);
+        assert_eq!(decoder.report().dropped[0].chunk_type, "FrSt");
+        assert!(matches!(
+            decoder.report().dropped[0].reason,
+            DropReason::LengthOverrun { .. }
+        ));
+    }
+
+    #[test]
+    fn test_decoder_handles_honestly_large_chunk() {
+        // Unlike the forged-length case above, a declared length backed by
+        // real data is read through successfully; `read_chunk_bytes` still
+        // materializes the whole chunk in `rest` to do it; see the
+        // read_chunk_bytes doc comment.
+        let big = chunk_from_strings("BiGc", &"x".repeat(64 * 1024));
+        let bytes: Vec<u8> = Png::STANDARD_HEADER
+            .iter()
+            .copied()
+            .chain(big.as_bytes())
+            .collect();
+
+        let mut decoder = PngDecoder::new(bytes.as_slice());
+        let chunk = decoder.next().unwrap().unwrap();
+
+        assert_eq!(chunk.chunk_type().to_string(), "BiGc");
+        assert_eq!(chunk.data().len(), 64 * 1024);
+        assert!(decoder.report().is_clean());
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt::Display;
+
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+use crate::Result;
+
+/// `index: u32` + `total: u32`, in front of every segment's payload.
+const HEADER_LEN: usize = 8;
+
+/// Default cap on how much payload goes in a single chunk before the
+/// message is split across more chunks of the same type, in the style of
+/// HTTP's `Transfer-Encoding: chunked`.
+pub const DEFAULT_MAX_CHUNK_SIZE: usize = 8 * 1024;
+
+/// Splits `payload` into one or more chunks of `chunk_type`, each prefixed
+/// with a `(sequence index, total count)` header so [`join_chunks`] can
+/// put them back together regardless of the order they're read in.
+///
+/// `max_chunk_size` bounds the size of each chunk's data, header included;
+/// a `max_chunk_size` of 8 or less still puts one payload byte in each
+/// chunk, so those chunks hold 9 bytes of data. A message that fits in one
+/// chunk still gets the header, so every chunk produced here can be
+/// reassembled the same way.
+pub fn split_into_chunks(chunk_type: &ChunkType, payload: &[u8], max_chunk_size: usize) -> Vec<Chunk> {
+    let max_segment_len = max_chunk_size.saturating_sub(HEADER_LEN).max(1);
+
+    let segments: Vec<&[u8]> = if payload.is_empty() {
+        vec![&[]]
+    } else {
+        payload.chunks(max_segment_len).collect()
+    };
+
+    let total = segments.len() as u32;
+
+    segments
+        .into_iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            let mut data = Vec::with_capacity(HEADER_LEN + segment.len());
+            data.extend_from_slice(&(index as u32).to_be_bytes());
+            data.extend_from_slice(&total.to_be_bytes());
+            data.extend_from_slice(segment);
+            Chunk::new(chunk_type.clone(), data)
+        })
+        .collect()
+}
+
+/// If `data`'s leading bytes parse as the first segment (index 0) of a
+/// chunked-transfer message, returns the total segment count its header
+/// declares. Returns `None` for anything else, meaning `data` predates
+/// this framing (or isn't a first segment) and should be treated as a
+/// complete, unframed message in its own right.
+pub fn leading_segment_total(data: &[u8]) -> Option<u32> {
+    match parse_segment(data) {
+        Ok((0, total, _)) if total >= 1 => Some(total),
+        _ => None,
+    }
+}
+
+/// Whether `collected` (every chunk of the requested type read so far, in
+/// file order) already holds a complete message, so a caller reading
+/// chunks one at a time can stop without parsing the rest of the stream.
+pub fn segments_complete(collected: &[&Chunk]) -> bool {
+    match collected.first() {
+        None => false,
+        Some(first) => match leading_segment_total(first.data()) {
+            Some(total) => collected.len() as u32 >= total,
+            // An unframed legacy chunk is already the whole message.
+            None => true,
+        },
+    }
+}
+
+/// Reassembles the payload previously split by [`split_into_chunks`],
+/// ordering segments by their sequence index regardless of the order
+/// `chunks` is given in.
+///
+/// A single chunk whose leading bytes don't parse as a first-segment
+/// `(0, total)` header (with `total >= 1`, see [`leading_segment_total`]) is
+/// treated as a legacy, unframed chunk predating this framing (or one
+/// produced by another PNGme-compatible tool): the whole chunk is the
+/// message, exactly as `chunk_by_type`/`data_as_string` treated it before.
+pub fn join_chunks(chunks: &[&Chunk]) -> Result<Vec<u8>> {
+    if chunks.is_empty() {
+        return Err(Box::new(FramingError::NoChunks));
+    }
+
+    if chunks.len() == 1 && leading_segment_total(chunks[0].data()).is_none() {
+        return Ok(chunks[0].data().to_vec());
+    }
+
+    let mut segments = chunks
+        .iter()
+        .map(|chunk| parse_segment(chunk.data()))
+        .collect::<Result<Vec<_>>>()?;
+
+    segments.sort_by_key(|(index, _, _)| *index);
+
+    let total = segments[0].1;
+    if segments.len() as u32 != total {
+        return Err(Box::new(FramingError::MissingSegments {
+            expected: total,
+            found: segments.len() as u32,
+        }));
+    }
+
+    for (expected_index, (index, _, _)) in segments.iter().enumerate() {
+        if *index != expected_index as u32 {
+            return Err(Box::new(FramingError::MissingSegments {
+                expected: total,
+                found: segments.len() as u32,
+            }));
+        }
+    }
+
+    Ok(segments.into_iter().flat_map(|(_, _, data)| data.to_vec()).collect())
+}
+
+fn parse_segment(data: &[u8]) -> Result<(u32, u32, &[u8])> {
+    if data.len() < HEADER_LEN {
+        return Err(Box::new(FramingError::Truncated(data.len())));
+    }
+
+    let index = u32::from_be_bytes(data[0..4].try_into().unwrap());
+    let total = u32::from_be_bytes(data[4..8].try_into().unwrap());
+    Ok((index, total, &data[HEADER_LEN..]))
+}
+
+/// Chunked-transfer framing errors
+#[derive(Debug)]
+pub enum FramingError {
+    /// No chunks of the requested type were found to reassemble.
+    NoChunks,
+
+    /// A chunk's data was too short to hold the segment header.
+    Truncated(usize),
+
+    /// The segments found don't form a complete, gap-free sequence.
+    MissingSegments { expected: u32, found: u32 },
+}
+
+impl std::error::Error for FramingError {}
+
+impl Display for FramingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FramingError::NoChunks => write!(f, "No chunks were given to reassemble"),
+            FramingError::Truncated(actual) => write!(
+                f,
+                "Expected at least {} bytes of segment header but received {}",
+                HEADER_LEN, actual
+            ),
+            FramingError::MissingSegments { expected, found } => write!(
+                f,
+                "Expected {} segments to reassemble the message but found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_round_trips_a_short_message() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let chunks = split_into_chunks(&chunk_type, b"hello", DEFAULT_MAX_CHUNK_SIZE);
+        assert_eq!(chunks.len(), 1);
+
+        let refs: Vec<&Chunk> = chunks.iter().collect();
+        assert_eq!(join_chunks(&refs).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_splits_a_long_message_across_chunks() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let payload: Vec<u8> = (0..5000).map(|i| (i % 251) as u8).collect();
+
+        let chunks = split_into_chunks(&chunk_type, &payload, 64);
+        assert!(chunks.len() > 1);
+
+        let refs: Vec<&Chunk> = chunks.iter().collect();
+        assert_eq!(join_chunks(&refs).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_reassembles_out_of_order_segments() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let payload: Vec<u8> = (0..500).map(|i| (i % 251) as u8).collect();
+
+        let mut chunks = split_into_chunks(&chunk_type, &payload, 64);
+        chunks.reverse();
+
+        let refs: Vec<&Chunk> = chunks.iter().collect();
+        assert_eq!(join_chunks(&refs).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_missing_segment_errors() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let payload: Vec<u8> = (0..500).map(|i| (i % 251) as u8).collect();
+
+        let mut chunks = split_into_chunks(&chunk_type, &payload, 64);
+        chunks.pop();
+
+        let refs: Vec<&Chunk> = chunks.iter().collect();
+        assert!(join_chunks(&refs).is_err());
+    }
+
+    #[test]
+    fn test_legacy_unframed_chunk_decodes_as_raw_message() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let legacy = Chunk::new(chunk_type, b"hello world secret".to_vec());
+
+        assert_eq!(join_chunks(&[&legacy]).unwrap(), b"hello world secret");
+    }
+
+    #[test]
+    fn test_empty_payload_round_trips() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let chunks = split_into_chunks(&chunk_type, &[], DEFAULT_MAX_CHUNK_SIZE);
+        assert_eq!(chunks.len(), 1);
+
+        let refs: Vec<&Chunk> = chunks.iter().collect();
+        assert_eq!(join_chunks(&refs).unwrap(), Vec::<u8>::new());
+    }
+}
@@ -1,28 +1,22 @@
-use std::{fs::{File, OpenOptions}, io::{Read, Write}, str::FromStr};
+use std::{fs::{File, OpenOptions}, io::{BufReader, BufWriter, Read}, str::FromStr};
 
-use args::*;
-use chunk::Chunk;
-use chunk_type::ChunkType;
 use clap::Parser;
-use png::Png;
-
-mod args;
-mod chunk;
-mod chunk_type;
-mod commands;
-mod png;
-
-pub type Error = Box<dyn std::error::Error>;
-pub type Result<T> = std::result::Result<T, Error>;
+use pngme::args::*;
+use pngme::chunk::Chunk;
+use pngme::chunk_type::ChunkType;
+use pngme::decoder::PngDecoder;
+use pngme::payload::Payload;
+use pngme::png::{DecodeReport, Png};
+use pngme::{framing, Result};
 
 fn main() -> Result<()> {
     let cli = MyArgs::parse();
 
     // You can check the value provided by positional arguments, or option arguments
     match &cli.commands {
-        Commands::Encode(params) => encode_msg(&params),
-        Commands::Decode(params) => decode_msg(&params),
-        Commands::Remove(params) => remove(&params),
+        Commands::Encode(params) => encode_msg(params),
+        Commands::Decode(params) => decode_msg(params),
+        Commands::Remove(params) => remove(params),
         Commands::Print(params) => print(&params.image_path)
     }
 
@@ -40,34 +34,86 @@ fn encode_msg(params: &EncodeCommand) {
     file.read_to_end(&mut bytes).expect("Error while reading file");
     let mut png = Png::try_from(&bytes[..]).unwrap();
 
-    png.append_chunk(Chunk::new(ChunkType::from_str(params.chunk_type.as_str()).unwrap(), params.message.as_bytes().to_vec()));
+    let data = if params.structured {
+        Payload {
+            message: Some(params.message.clone()),
+            ..Default::default()
+        }
+        .encode()
+    } else {
+        params.message.as_bytes().to_vec()
+    };
+
+    let chunk_type = ChunkType::from_str(params.chunk_type.as_str()).unwrap();
+    for chunk in framing::split_into_chunks(&chunk_type, &data, params.max_chunk_size) {
+        png.append_chunk(chunk);
+    }
 
     if let Some(output_file) = &params.output_file {
-        let mut f = File::create(output_file).unwrap();
-        f.write_all(&png.as_bytes()).expect("Something went wrong opening the file");
+        let f = File::create(output_file).unwrap();
+        png.write_to(&mut BufWriter::new(f))
+            .expect("Something went wrong opening the file");
     } else {
-        file.write_all(&png.as_bytes()).unwrap();
+        png.write_to(&mut BufWriter::new(&file)).unwrap();
     }
 }
 
 fn decode_msg(params: &DecodeCommand) {
-    let mut bytes = Vec::new();
-    let mut file = File::open(&params.image_path).unwrap();
-    file.read_to_end(&mut bytes).expect("Error while reading file");
-    let png = Png::try_from(&bytes[..]).unwrap();
-    let chunk = png.chunk_by_type(&params.chunk_type);
-    if let Some(data) = &chunk {
-        println!("Encoded message is: \"{}\"", data.data_as_string().unwrap())
+    let file = File::open(&params.image_path).unwrap();
+    let mut decoder = PngDecoder::new(BufReader::new(file));
+
+    // Only the chunks matching `chunk_type` are needed, so stop pulling
+    // from the decoder as soon as they form a complete message instead of
+    // parsing the rest of a potentially huge file.
+    let mut matching: Vec<Chunk> = Vec::new();
+    for chunk in &mut decoder {
+        let chunk = chunk.unwrap();
+        if chunk.chunk_type().to_string() == params.chunk_type {
+            matching.push(chunk);
+            let refs: Vec<&Chunk> = matching.iter().collect();
+            if framing::segments_complete(&refs) {
+                break;
+            }
+        }
+    }
+    warn_about_dropped_chunks(decoder.report());
+
+    if matching.is_empty() {
+        println!("There is no hidden message in this file.");
+        return;
+    }
+
+    let refs: Vec<&Chunk> = matching.iter().collect();
+    let data = match framing::join_chunks(&refs) {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("error: failed to reassemble message: {}", err);
+            return;
+        }
+    };
+
+    if params.structured {
+        match Payload::decode(&data) {
+            Ok(payload) => println!("Encoded payload is: {:?}", payload),
+            Err(err) => {
+                eprintln!(
+                    "warning: --structured was given but the chunk's data isn't a valid payload: {}",
+                    err
+                );
+                println!("Encoded message is: \"{}\"", String::from_utf8_lossy(&data));
+            }
+        }
     } else {
-        println!("There is no hidden message in this file.")
+        println!("Encoded message is: \"{}\"", String::from_utf8_lossy(&data));
     }
 }
 
 fn remove(params: &RemoveCommand) {
-    let mut bytes = Vec::new();
-    let mut file = File::open(&params.image_path).unwrap();
-    file.read_to_end(&mut bytes).expect("Error while reading file");
-    let mut png = Png::try_from(&bytes[..]).unwrap();
+    let file = File::open(&params.image_path).unwrap();
+    let mut decoder = PngDecoder::new(BufReader::new(file));
+    let chunks: Vec<Chunk> = (&mut decoder).map(|chunk| chunk.unwrap()).collect();
+    warn_about_dropped_chunks(decoder.report());
+    let mut png = Png::from_chunks(chunks);
 
     let removed_chunk = png.remove_chunk(&params.chunk_type).unwrap();
 
@@ -75,9 +121,20 @@ fn remove(params: &RemoveCommand) {
 }
 
 fn print(file_path: &String) {
-    let mut bytes = Vec::new();
-    let mut file = File::open(&file_path).unwrap();
-    file.read_to_end(&mut bytes).expect("Error while reading file");
-    let png = Png::try_from(&bytes[..]).unwrap();
+    let file = File::open(file_path).unwrap();
+    let mut decoder = PngDecoder::new(BufReader::new(file));
+    let chunks: Vec<Chunk> = (&mut decoder).map(|chunk| chunk.unwrap()).collect();
+    warn_about_dropped_chunks(decoder.report());
+
+    let png = Png::from_chunks(chunks);
     println!("{png}")
+}
+
+fn warn_about_dropped_chunks(report: &DecodeReport) {
+    for dropped in &report.dropped {
+        eprintln!(
+            "warning: dropped chunk {}: {}",
+            dropped.chunk_type, dropped.reason
+        );
+    }
 }
\ No newline at end of file
@@ -0,0 +1,207 @@
+use std::fmt::Display;
+
+use crate::{Error, Result};
+
+const TAG_MESSAGE: u8 = 1;
+const TAG_AUTHOR: u8 = 2;
+const TAG_UNIX_TIMESTAMP: u8 = 3;
+const TAG_MIME_TYPE: u8 = 4;
+
+/// Structured metadata that can be carried inside a chunk's data, encoded
+/// as a sequence of `tag, u32 length, value` fields (similar in spirit to
+/// a DER/ASN.1 TLV encoding). Any field left as `None` is simply omitted
+/// from the encoding.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Payload {
+    pub message: Option<String>,
+    pub author: Option<String>,
+    pub unix_timestamp: Option<u64>,
+    pub mime_type: Option<String>,
+}
+
+impl Payload {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        if let Some(message) = &self.message {
+            encode_field(&mut buf, TAG_MESSAGE, message.as_bytes());
+        }
+        if let Some(author) = &self.author {
+            encode_field(&mut buf, TAG_AUTHOR, author.as_bytes());
+        }
+        if let Some(unix_timestamp) = self.unix_timestamp {
+            encode_field(&mut buf, TAG_UNIX_TIMESTAMP, &unix_timestamp.to_be_bytes());
+        }
+        if let Some(mime_type) = &self.mime_type {
+            encode_field(&mut buf, TAG_MIME_TYPE, mime_type.as_bytes());
+        }
+
+        buf
+    }
+
+    /// Decodes a payload previously produced by [`Payload::encode`].
+    ///
+    /// Each declared field length is checked against what is left in the
+    /// buffer, and a tag this version doesn't recognize is skipped (via
+    /// its own length prefix) instead of causing an error, so payloads
+    /// gain new fields without breaking older readers.
+    pub fn decode(buf: &[u8]) -> Result<Self> {
+        let mut payload = Self::default();
+        let mut offset = 0;
+
+        while offset < buf.len() {
+            let tag = buf[offset];
+            offset += 1;
+
+            let len_bytes = take(buf, offset..(offset + 4))?;
+            let len = u32::from_be_bytes(len_bytes.try_into().unwrap()) as usize;
+            offset += 4;
+
+            let value = take(buf, offset..(offset + len))?;
+            offset += len;
+
+            match tag {
+                TAG_MESSAGE => payload.message = Some(String::from_utf8_lossy(value).into_owned()),
+                TAG_AUTHOR => payload.author = Some(String::from_utf8_lossy(value).into_owned()),
+                TAG_UNIX_TIMESTAMP => {
+                    let bytes: [u8; 8] = value
+                        .try_into()
+                        .map_err(|_| Box::new(PayloadError::InvalidTimestampLength(value.len())) as Error)?;
+                    payload.unix_timestamp = Some(u64::from_be_bytes(bytes));
+                }
+                TAG_MIME_TYPE => payload.mime_type = Some(String::from_utf8_lossy(value).into_owned()),
+                _ => {
+                    // Unknown tag: already skipped past, since we always
+                    // advance by its declared length above.
+                }
+            }
+        }
+
+        Ok(payload)
+    }
+}
+
+fn encode_field(buf: &mut Vec<u8>, tag: u8, value: &[u8]) {
+    buf.push(tag);
+    buf.extend_from_slice(&(value.len() as u32).to_be_bytes());
+    buf.extend_from_slice(value);
+}
+
+/// Returns `buf[range]`, or a [`PayloadError::Truncated`] instead of
+/// panicking if `range` runs past the end of `buf`.
+fn take(buf: &[u8], range: std::ops::Range<usize>) -> Result<&[u8]> {
+    if range.end > buf.len() {
+        return Err(Box::new(PayloadError::Truncated {
+            offset: range.start,
+            needed: range.end - range.start,
+            available: buf.len().saturating_sub(range.start),
+        }));
+    }
+
+    Ok(&buf[range])
+}
+
+/// Payload parsing errors
+#[derive(Debug)]
+pub enum PayloadError {
+    /// A field's declared length ran past the end of the buffer.
+    Truncated {
+        offset: usize,
+        needed: usize,
+        available: usize,
+    },
+
+    /// A `UnixTimestamp` field wasn't the expected 8 bytes.
+    InvalidTimestampLength(usize),
+}
+
+impl std::error::Error for PayloadError {}
+
+impl Display for PayloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PayloadError::Truncated {
+                offset,
+                needed,
+                available,
+            } => write!(
+                f,
+                "Expected {} bytes at offset {} but only {} were available",
+                needed, offset, available
+            ),
+            PayloadError::InvalidTimestampLength(actual) => write!(
+                f,
+                "Expected 8 bytes for a UnixTimestamp field but received {}",
+                actual
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_all_fields() {
+        let payload = Payload {
+            message: Some("hidden message".to_string()),
+            author: Some("RuSt".to_string()),
+            unix_timestamp: Some(1_700_000_000),
+            mime_type: Some("text/plain".to_string()),
+        };
+
+        let encoded = payload.encode();
+        let decoded = Payload::decode(&encoded).unwrap();
+
+        assert_eq!(payload, decoded);
+    }
+
+    #[test]
+    fn test_round_trip_message_only() {
+        let payload = Payload {
+            message: Some("just a message".to_string()),
+            ..Default::default()
+        };
+
+        let encoded = payload.encode();
+        let decoded = Payload::decode(&encoded).unwrap();
+
+        assert_eq!(payload, decoded);
+    }
+
+    #[test]
+    fn test_empty_payload_round_trips() {
+        let payload = Payload::default();
+        let decoded = Payload::decode(&payload.encode()).unwrap();
+        assert_eq!(payload, decoded);
+    }
+
+    #[test]
+    fn test_unknown_tag_is_skipped() {
+        let mut encoded = Payload {
+            message: Some("kept".to_string()),
+            ..Default::default()
+        }
+        .encode();
+
+        // Append a field with a tag no version of this code defines.
+        encode_field(&mut encoded, 99, b"ignore me");
+
+        let decoded = Payload::decode(&encoded).unwrap();
+        assert_eq!(decoded.message, Some("kept".to_string()));
+    }
+
+    #[test]
+    fn test_truncated_length_errors() {
+        let encoded = vec![TAG_MESSAGE, 0, 0, 0, 10, b'h', b'i'];
+        assert!(Payload::decode(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_wrong_timestamp_length_errors() {
+        let mut encoded = Vec::new();
+        encode_field(&mut encoded, TAG_UNIX_TIMESTAMP, &[1, 2, 3]);
+        assert!(Payload::decode(&encoded).is_err());
+    }
+}
@@ -0,0 +1,360 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use crate::chunk::Chunk;
+use crate::{Error, Result};
+
+#[derive(Debug, Clone)]
+pub struct Png {
+    chunks: Vec<Chunk>,
+}
+
+impl Png {
+    pub const STANDARD_HEADER: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+    pub fn from_chunks(chunks: Vec<Chunk>) -> Self {
+        Self { chunks }
+    }
+
+    pub fn append_chunk(&mut self, chunk: Chunk) {
+        self.chunks.push(chunk);
+    }
+
+    pub fn remove_chunk(&mut self, chunk_type: &str) -> Result<Chunk> {
+        let index = self
+            .chunks
+            .iter()
+            .position(|chunk| chunk.chunk_type().to_string() == chunk_type)
+            .ok_or_else(|| Box::new(PngError::ChunkNotFound(chunk_type.to_string())) as Error)?;
+
+        Ok(self.chunks.remove(index))
+    }
+
+    pub fn header(&self) -> &[u8; 8] {
+        &Self::STANDARD_HEADER
+    }
+
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+
+    pub fn chunk_by_type(&self, chunk_type: &str) -> Option<&Chunk> {
+        self.chunks
+            .iter()
+            .find(|chunk| chunk.chunk_type().to_string() == chunk_type)
+    }
+
+    /// All chunks with the given type, in file order. A message that was
+    /// split across several same-type chunks needs every one of these to
+    /// be reassembled, unlike [`Png::chunk_by_type`] which only ever
+    /// returns the first.
+    pub fn chunks_by_type(&self, chunk_type: &str) -> Vec<&Chunk> {
+        self.chunks
+            .iter()
+            .filter(|chunk| chunk.chunk_type().to_string() == chunk_type)
+            .collect()
+    }
+
+    /// Writes the signature and every chunk's wire format to `w` without
+    /// buffering the whole png in memory first.
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&Self::STANDARD_HEADER)?;
+        for chunk in &self.chunks {
+            chunk.write_to(w)?;
+        }
+        Ok(())
+    }
+
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.write_to(&mut buf)
+            .expect("writing to a Vec<u8> cannot fail");
+        buf
+    }
+}
+
+impl TryFrom<&[u8]> for Png {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < 8 {
+            return Err(Box::new(PngError::ByteLengthError(bytes.len())));
+        }
+
+        let (header, mut remaining) = bytes.split_at(8);
+        if header != Self::STANDARD_HEADER {
+            return Err(Box::new(PngError::InvalidHeader));
+        }
+
+        let mut chunks = Vec::new();
+        while !remaining.is_empty() {
+            let chunk = Chunk::try_from(remaining)?;
+            remaining = &remaining[(chunk.length() as usize + 12)..];
+            chunks.push(chunk);
+        }
+
+        Ok(Self { chunks })
+    }
+}
+
+impl Display for Png {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Png {{")?;
+        for chunk in &self.chunks {
+            writeln!(f, "  {},", chunk)?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// Summary of the chunks a [`crate::decoder::PngDecoder`] decode had to skip.
+#[derive(Debug, Clone, Default)]
+pub struct DecodeReport {
+    pub dropped: Vec<DroppedChunk>,
+}
+
+impl DecodeReport {
+    /// True if every chunk in the stream was recovered intact.
+    pub fn is_clean(&self) -> bool {
+        self.dropped.is_empty()
+    }
+}
+
+/// A chunk that a lenient decode could not keep.
+#[derive(Debug, Clone)]
+pub struct DroppedChunk {
+    /// The chunk type the stream claimed, even though the chunk itself
+    /// could not be trusted.
+    pub chunk_type: String,
+    pub reason: DropReason,
+}
+
+#[derive(Debug, Clone)]
+pub enum DropReason {
+    /// The stored CRC did not match the CRC computed over the chunk's bytes.
+    CrcMismatch { crc_val: u32, crc_sum: u32 },
+
+    /// The chunk's declared length ran past the end of the buffer.
+    LengthOverrun { declared: u32, available: usize },
+}
+
+impl Display for DropReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DropReason::CrcMismatch { crc_val, crc_sum } => write!(
+                f,
+                "stored crc {} does not match computed crc {}",
+                crc_val, crc_sum
+            ),
+            DropReason::LengthOverrun { declared, available } => write!(
+                f,
+                "declared length {} leaves only {} bytes available",
+                declared, available
+            ),
+        }
+    }
+}
+
+/// Png parsing errors
+#[derive(Debug)]
+pub enum PngError {
+    /// The provided bytes are shorter than the 8-byte png header
+    ByteLengthError(usize),
+
+    /// The provided bytes do not start with the png signature
+    InvalidHeader,
+
+    /// No chunk with the requested type exists in this png
+    ChunkNotFound(String),
+}
+
+impl std::error::Error for PngError {}
+
+impl Display for PngError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PngError::ByteLengthError(actual) => write!(
+                f,
+                "Expected at least 8 bytes but received {} when parsing png",
+                actual
+            ),
+            PngError::InvalidHeader => {
+                write!(f, "Provided bytes do not start with the png header")
+            }
+            PngError::ChunkNotFound(chunk_type) => {
+                write!(f, "No chunk with type {} was found", chunk_type)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_type::ChunkType;
+    use std::str::FromStr;
+
+    fn testing_chunks() -> Vec<Chunk> {
+        vec![
+            chunk_from_strings("FrSt", "I am the first chunk").unwrap(),
+            chunk_from_strings("miDl", "I am another chunk").unwrap(),
+            chunk_from_strings("LASt", "I am the last chunk").unwrap(),
+        ]
+    }
+
+    fn chunk_from_strings(chunk_type: &str, data: &str) -> Result<Chunk> {
+        let chunk_type = ChunkType::from_str(chunk_type)?;
+        let data: Vec<u8> = data.bytes().collect();
+
+        Ok(Chunk::new(chunk_type, data))
+    }
+
+    fn testing_png() -> Png {
+        let chunks = testing_chunks();
+        Png::from_chunks(chunks)
+    }
+
+    #[test]
+    fn test_from_chunks() {
+        let chunks = testing_chunks();
+        let png = Png::from_chunks(chunks);
+
+        assert_eq!(png.chunks().len(), 3);
+    }
+
+    #[test]
+    fn test_valid_from_bytes() {
+        let chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+
+        let bytes: Vec<u8> = Png::STANDARD_HEADER
+            .iter()
+            .chain(chunk_bytes.iter())
+            .copied()
+            .collect();
+
+        let png = Png::try_from(bytes.as_ref()).unwrap();
+
+        assert_eq!(png.chunks().len(), 3);
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        let chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+
+        let bytes: Vec<u8> = [13, 80, 78, 71, 13, 10, 26, 10]
+            .iter()
+            .chain(chunk_bytes.iter())
+            .copied()
+            .collect();
+
+        let png = Png::try_from(bytes.as_ref());
+
+        assert!(png.is_err());
+    }
+
+    #[test]
+    fn test_invalid_chunk() {
+        let mut chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+
+        let mut bytes: Vec<u8> = Png::STANDARD_HEADER.to_vec();
+
+        bytes.append(&mut chunk_bytes);
+
+        let last = bytes.len() - 1;
+        bytes[last] = bytes[last].wrapping_add(1);
+
+        let png = Png::try_from(bytes.as_ref());
+
+        assert!(png.is_err());
+    }
+
+    #[test]
+    fn test_list_chunks() {
+        let png = testing_png();
+        let chunks = png.chunks();
+        assert_eq!(chunks.len(), 3);
+    }
+
+    #[test]
+    fn test_chunk_by_type() {
+        let png = testing_png();
+        let chunk = png.chunk_by_type("FrSt").unwrap();
+        assert_eq!(&chunk.chunk_type().to_string(), "FrSt");
+        assert_eq!(&chunk.data_as_string().unwrap(), "I am the first chunk");
+    }
+
+    #[test]
+    fn test_chunks_by_type_returns_every_match() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("FrSt", "I am a second FrSt chunk").unwrap());
+
+        let chunks = png.chunks_by_type("FrSt");
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(png.chunks_by_type("NoPe").len(), 0);
+    }
+
+    #[test]
+    fn test_append_chunk() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("TeSt", "Message").unwrap());
+        let chunk = png.chunk_by_type("TeSt").unwrap();
+        assert_eq!(&chunk.chunk_type().to_string(), "TeSt");
+        assert_eq!(&chunk.data_as_string().unwrap(), "Message");
+    }
+
+    #[test]
+    fn test_remove_chunk() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("TeSt", "Message").unwrap());
+        png.remove_chunk("TeSt").unwrap();
+        let chunk = png.chunk_by_type("TeSt");
+        assert!(chunk.is_none());
+    }
+
+    #[test]
+    fn test_chunk_by_type_is_none_when_missing() {
+        let png = testing_png();
+        assert!(png.chunk_by_type("NoPe").is_none());
+    }
+
+    #[test]
+    fn test_as_bytes() {
+        let png = testing_png();
+        let actual = png.as_bytes();
+
+        let expected: Vec<u8> = Png::STANDARD_HEADER
+            .iter()
+            .copied()
+            .chain(testing_chunks().iter().flat_map(Chunk::as_bytes))
+            .collect();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_png_trait_impls() {
+        let chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+
+        let bytes: Vec<u8> = Png::STANDARD_HEADER
+            .iter()
+            .chain(chunk_bytes.iter())
+            .copied()
+            .collect();
+
+        let png: Png = TryFrom::try_from(bytes.as_ref()).unwrap();
+
+        let _png_string = format!("{}", png);
+    }
+}